@@ -0,0 +1,133 @@
+//! A small disk-backed cache that remembers each response's `ETag` and
+//! replays the cached body on a `304 Not Modified`, so repeated runs against
+//! the same org don't pay for requests the server will tell us are
+//! unchanged.
+//!
+//! `If-None-Match`/`304` is a GET/REST mechanism, so this only pays off for
+//! [`super::forge::gitlab::GitLab`] and [`super::forge::forgejo::Forgejo`],
+//! whose list/detail endpoints are plain GETs.
+//! [`super::forge::github::GitHub`] posts a GraphQL query to a single
+//! `/graphql` endpoint, which GitHub does not return `ETag`/`304` for, so
+//! `post_json_text` always gets a fresh `200` — caching it here would mean
+//! either never invalidating (stale results) or reinventing a freshness
+//! check GitHub doesn't give us, so we don't pretend to.
+
+use std::{env, fs, path::PathBuf};
+
+use reqwest::{
+    header::{HeaderValue, ETAG, IF_NONE_MATCH},
+    Client, RequestBuilder, StatusCode,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{retry, Result};
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    url: String,
+    etag: String,
+    body: String,
+}
+
+/// Wraps a [`Client`] with an on-disk cache keyed by request URL (and, for
+/// POST requests such as our GraphQL queries, the request body too, since
+/// they all share one endpoint).
+///
+/// Set `GH_STATS_CACHE=0` (or pass `--no-cache`) to disable it entirely,
+/// in which case requests are sent straight through with no caching.
+pub struct CachedClient {
+    client: Client,
+    dir: Option<PathBuf>,
+}
+
+impl CachedClient {
+    pub fn new(client: Client, enabled: bool) -> Result<Self> {
+        let dir = if enabled {
+            let dir = cache_dir();
+            fs::create_dir_all(&dir)?;
+            Some(dir)
+        } else {
+            None
+        };
+        Ok(CachedClient { client, dir })
+    }
+
+    pub fn enabled_by_env() -> bool {
+        env::var("GH_STATS_CACHE").map(|v| v != "0").unwrap_or(true)
+    }
+
+    fn entry_path(&self, key: &str) -> Option<PathBuf> {
+        self.dir.as_ref().map(|dir| dir.join(format!("{:x}.json", hash_key(key))))
+    }
+
+    /// GET `url`, transparently attaching `If-None-Match` and serving the
+    /// cached body on a 304.
+    pub async fn get_text(&self, url: &str) -> Result<String> {
+        self.send_cached(url, self.client.get(url)).await
+    }
+
+    /// POST `url` with a JSON body, keyed by `url` + the body itself since
+    /// our GraphQL endpoint is a single URL for every query.
+    pub async fn post_json_text(&self, url: &str, body: &impl Serialize) -> Result<String> {
+        let body = serde_json::to_string(body)?;
+        let key = format!("{url}#{body}");
+        self.send_cached(&key, self.client.post(url).header("content-type", "application/json").body(body))
+            .await
+    }
+
+    async fn send_cached(&self, key: &str, mut req: RequestBuilder) -> Result<String> {
+        let cached = self.entry_path(key).and_then(|path| {
+            fs::read_to_string(&path).ok().and_then(|s| serde_json::from_str::<CacheEntry>(&s).ok())
+        });
+
+        if let Some(entry) = &cached {
+            req = req.header(IF_NONE_MATCH, HeaderValue::from_str(&entry.etag)?);
+        }
+
+        let resp = retry::send_with_retry(|| {
+            req.try_clone().expect("request body must be cloneable to retry").send()
+        })
+        .await?;
+        log_rate_limit(&resp);
+
+        if resp.status() == StatusCode::NOT_MODIFIED {
+            log::debug!("cache hit (304) for {key}");
+            return Ok(cached.expect("304 implies a cached entry was sent").body);
+        }
+
+        let etag = resp.headers().get(ETAG).and_then(|v| v.to_str().ok()).map(str::to_string);
+        let body = resp.error_for_status()?.text().await?;
+
+        if let (Some(etag), Some(path)) = (etag, self.entry_path(key)) {
+            let entry = CacheEntry { url: key.to_string(), etag, body: body.clone() };
+            fs::write(path, serde_json::to_string(&entry)?)?;
+        }
+
+        Ok(body)
+    }
+}
+
+fn log_rate_limit(resp: &reqwest::Response) {
+    if let Some(remaining) = resp.headers().get("x-ratelimit-remaining").and_then(|v| v.to_str().ok()) {
+        if remaining.parse::<u32>().map(|n| n < 100).unwrap_or(false) {
+            log::warn!("approaching GitHub rate limit: {remaining} requests remaining");
+        }
+    }
+}
+
+fn cache_dir() -> PathBuf {
+    env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(|| PathBuf::from(".cache"))
+        .join("gh-stats")
+}
+
+/// Cheap, dependency-free string hash for cache filenames; collisions just
+/// mean an extra cache miss, not a correctness problem.
+fn hash_key(s: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    s.hash(&mut hasher);
+    hasher.finish()
+}
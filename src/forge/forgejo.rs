@@ -0,0 +1,129 @@
+//! Forgejo/Gitea backend. The pulls API is per-repo, so we first list the
+//! org's repos, then page through each repo's closed pulls and check the
+//! reviews endpoint for an approval.
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::{stream, StreamExt, TryStreamExt};
+use serde::Deserialize;
+
+use crate::{cache::CachedClient, Result};
+
+use super::{Forge, PRInfo};
+
+pub struct Forgejo {
+    client: CachedClient,
+    base_url: String,
+    concurrency: usize,
+}
+
+impl Forgejo {
+    pub fn new(client: CachedClient, base_url: String, concurrency: usize) -> Self {
+        Forgejo { client, base_url, concurrency }
+    }
+
+    async fn repos(&self, org: &str) -> Result<Vec<String>> {
+        let base = self.base_url.trim_end_matches('/');
+        let mut names = Vec::new();
+        let mut page = 1;
+        loop {
+            let url = format!("{base}/api/v1/orgs/{org}/repos?page={page}&limit=50");
+            let repos: Vec<Repo> = serde_json::from_str(&self.client.get_text(&url).await?)?;
+            if repos.is_empty() {
+                break;
+            }
+            names.extend(repos.into_iter().map(|r| r.name));
+            page += 1;
+        }
+        Ok(names)
+    }
+
+    async fn pulls(&self, org: &str, repo: &str) -> Result<Vec<Pull>> {
+        let base = self.base_url.trim_end_matches('/');
+        let mut all = Vec::new();
+        let mut page = 1;
+        loop {
+            let url = format!("{base}/api/v1/repos/{org}/{repo}/pulls?state=closed&page={page}&limit=50");
+            let pulls: Vec<Pull> = serde_json::from_str(&self.client.get_text(&url).await?)?;
+            if pulls.is_empty() {
+                break;
+            }
+            all.extend(pulls);
+            page += 1;
+        }
+        Ok(all)
+    }
+
+    async fn approvers(&self, org: &str, repo: &str, index: u64) -> Result<Vec<String>> {
+        let base = self.base_url.trim_end_matches('/');
+        let url = format!("{base}/api/v1/repos/{org}/{repo}/pulls/{index}/reviews");
+        let reviews: Vec<Review> = serde_json::from_str(&self.client.get_text(&url).await?)?;
+        Ok(reviews.into_iter().filter(|r| r.state == "APPROVED").map(|r| r.user.login).collect())
+    }
+
+    async fn pr_info(
+        &self,
+        org: &str,
+        repo: &str,
+        pull: Pull,
+        since: Option<NaiveDate>,
+        until: Option<NaiveDate>,
+    ) -> Result<Option<PRInfo>> {
+        if !pull.merged {
+            return Ok(None);
+        }
+        let merged_at = pull.merged_at.unwrap_or(DateTime::<Utc>::MIN_UTC);
+        if since.is_some_and(|d| merged_at.date_naive() < d) || until.is_some_and(|d| merged_at.date_naive() > d) {
+            return Ok(None);
+        }
+        let reviewers = self.approvers(org, repo, pull.number).await?;
+        Ok(Some(PRInfo {
+            repository: format!("{org}/{repo}"),
+            author: pull.user.login,
+            approved: !reviewers.is_empty(),
+            reviewers,
+            merged_at,
+        }))
+    }
+}
+
+#[derive(Deserialize)]
+struct Repo {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Pull {
+    number: u64,
+    merged: bool,
+    merged_at: Option<DateTime<Utc>>,
+    user: User,
+}
+
+#[derive(Deserialize)]
+struct User {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct Review {
+    state: String,
+    user: User,
+}
+
+#[async_trait]
+impl Forge for Forgejo {
+    async fn merged_prs(&self, org: &str, since: Option<NaiveDate>, until: Option<NaiveDate>) -> Result<Vec<PRInfo>> {
+        let mut all = Vec::new();
+        for repo in self.repos(org).await? {
+            let pulls = self.pulls(org, &repo).await?;
+            let repo_prs: Vec<PRInfo> = stream::iter(pulls.into_iter().map(|pull| self.pr_info(org, &repo, pull, since, until)))
+                .buffer_unordered(self.concurrency.max(1))
+                .try_filter_map(|pr| async move { Ok(pr) })
+                .try_collect()
+                .await?;
+            all.extend(repo_prs);
+        }
+        Ok(all)
+    }
+}
@@ -0,0 +1,116 @@
+//! GitHub backend: a single-pass GraphQL collection of merged PRs, paging
+//! via `pageInfo` instead of computing a page count up front.
+//!
+//! Review state comes back inline with each PR in the same query, so unlike
+//! [`super::gitlab::GitLab`]/[`super::forgejo::Forgejo`] there's no per-PR
+//! follow-up request to fan out; paging itself is strictly sequential since
+//! each page's cursor depends on the previous page's. `--concurrency` has no
+//! effect on this backend.
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use graphql_client::{GraphQLQuery, Response};
+
+use crate::{cache::CachedClient, Result};
+
+use super::{Forge, PRInfo};
+
+#[allow(non_camel_case_types)]
+type DateTime = chrono::DateTime<chrono::Utc>;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/github_schema.graphql",
+    query_path = "graphql/merged_prs.graphql",
+    response_derives = "Debug"
+)]
+struct MergedPrs;
+
+pub struct GitHub {
+    client: CachedClient,
+    base_url: String,
+}
+
+impl GitHub {
+    pub fn new(client: CachedClient, base_url: String) -> Self {
+        GitHub { client, base_url }
+    }
+
+    fn graphql_url(&self) -> String {
+        format!("{}/graphql", self.base_url.trim_end_matches('/'))
+    }
+}
+
+type Reviews = Option<merged_prs::MergedPrsSearchNodesOnPullRequestReviews>;
+
+/// Whether any review is `APPROVED`, and the logins of the approvers that
+/// still have one (a deleted/ghost account still counts toward `approved`
+/// even though it contributes no login to the reviewer list).
+fn approvals(reviews: Reviews) -> (bool, Vec<String>) {
+    let approved_reviews: Vec<_> = reviews
+        .map(|r| r.nodes.unwrap_or_default())
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .filter(|review| matches!(review.state, merged_prs::PullRequestReviewState::APPROVED))
+        .collect();
+    let approved = !approved_reviews.is_empty();
+    let reviewers = approved_reviews.into_iter().filter_map(|review| review.author.map(|a| a.login)).collect();
+    (approved, reviewers)
+}
+
+#[async_trait]
+impl Forge for GitHub {
+    /// Page through `search(query: "is:pr is:merged org:$org", type: ISSUE,
+    /// ...)` via its `pageInfo` cursor, collecting review/author detail in
+    /// one pass.
+    async fn merged_prs(&self, org: &str, since: Option<NaiveDate>, until: Option<NaiveDate>) -> Result<Vec<PRInfo>> {
+        let mut query = format!("is:pr is:merged org:{org}");
+        if let Some(since) = since {
+            query.push_str(&format!(" merged:>={since}"));
+        }
+        if let Some(until) = until {
+            query.push_str(&format!(" merged:<={until}"));
+        }
+        let mut all = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let variables = merged_prs::Variables {
+                query: query.clone(),
+                cursor: cursor.clone(),
+            };
+            let body = MergedPrs::build_query(variables);
+            let text = self.client.post_json_text(&self.graphql_url(), &body).await?;
+            let resp: Response<merged_prs::ResponseData> = serde_json::from_str(&text)?;
+
+            if let Some(errors) = resp.errors.filter(|e| !e.is_empty()) {
+                anyhow::bail!("GraphQL errors: {errors:?}");
+            }
+            let search = resp
+                .data
+                .ok_or_else(|| anyhow::anyhow!("GraphQL response had no data"))?
+                .search;
+
+            for node in search.nodes.unwrap_or_default().into_iter().flatten() {
+                if let merged_prs::MergedPrsSearchNodes::PullRequest(pr) = node {
+                    let (approved, reviewers) = approvals(pr.reviews);
+                    all.push(PRInfo {
+                        repository: pr.repository.name_with_owner,
+                        author: pr.author.map(|a| a.login).unwrap_or_default(),
+                        approved,
+                        reviewers,
+                        merged_at: pr.merged_at.unwrap_or(chrono::DateTime::<chrono::Utc>::MIN_UTC),
+                    });
+                }
+            }
+
+            if !search.page_info.has_next_page {
+                break;
+            }
+            cursor = search.page_info.end_cursor;
+        }
+
+        Ok(all)
+    }
+}
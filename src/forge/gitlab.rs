@@ -0,0 +1,127 @@
+//! GitLab backend: group merge requests plus the per-MR approvals endpoint,
+//! since GitLab's merge request list doesn't carry approval state itself.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use futures::{stream, StreamExt, TryStreamExt};
+use serde::Deserialize;
+
+use crate::{cache::CachedClient, Result};
+
+use super::{Forge, PRInfo};
+
+pub struct GitLab {
+    client: CachedClient,
+    base_url: String,
+    concurrency: usize,
+}
+
+impl GitLab {
+    pub fn new(client: CachedClient, base_url: String, concurrency: usize) -> Self {
+        GitLab { client, base_url, concurrency }
+    }
+
+    async fn pr_info(&self, base: &str, mr: MergeRequest) -> Result<PRInfo> {
+        let approvals_url = format!("{base}/api/v4/projects/{}/merge_requests/{}/approvals", mr.project_id, mr.iid);
+        let approvals: Approvals = serde_json::from_str(&self.client.get_text(&approvals_url).await?)?;
+        let reviewers = approvals.approved_by.into_iter().map(|a| a.user.username).collect();
+        Ok(PRInfo {
+            repository: repo_from_reference(mr.references.full),
+            author: mr.author.username,
+            approved: approvals.approved,
+            reviewers,
+            merged_at: mr.merged_at.unwrap_or(DateTime::<Utc>::MIN_UTC),
+        })
+    }
+}
+
+/// "group/project!123" -> "group/project"
+fn repo_from_reference(full: String) -> String {
+    full.rsplit_once('!').map(|(repo, _)| repo.to_string()).unwrap_or(full)
+}
+
+#[derive(Deserialize)]
+struct MergeRequest {
+    project_id: u64,
+    iid: u64,
+    author: Author,
+    references: References,
+    merged_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+struct Author {
+    username: String,
+}
+
+#[derive(Deserialize)]
+struct References {
+    full: String,
+}
+
+#[derive(Deserialize)]
+struct Approvals {
+    approved: bool,
+    approved_by: Vec<ApprovedBy>,
+}
+
+#[derive(Deserialize)]
+struct ApprovedBy {
+    user: Author,
+}
+
+#[async_trait]
+impl Forge for GitLab {
+    async fn merged_prs(&self, org: &str, since: Option<NaiveDate>, until: Option<NaiveDate>) -> Result<Vec<PRInfo>> {
+        let base = self.base_url.trim_end_matches('/');
+        let mut all = Vec::new();
+        let mut page = 1;
+
+        let mut filters = String::new();
+        if let Some(since) = since {
+            filters.push_str(&format!("&merged_after={since}"));
+        }
+        if let Some(until) = until {
+            // `merged_before` is midnight-exclusive, so bump to the next day
+            // to make `--until` inclusive of that whole day, matching
+            // GitHub's `merged:<=` and Forgejo's date comparison.
+            filters.push_str(&format!("&merged_before={}", until + Duration::days(1)));
+        }
+
+        loop {
+            let url = format!(
+                "{base}/api/v4/groups/{org}/merge_requests?state=merged&scope=all&per_page=100&page={page}{filters}"
+            );
+            let text = self.client.get_text(&url).await?;
+            let mrs: Vec<MergeRequest> = serde_json::from_str(&text)?;
+            if mrs.is_empty() {
+                break;
+            }
+
+            let page_prs: Vec<PRInfo> = stream::iter(mrs.into_iter().map(|mr| self.pr_info(base, mr)))
+                .buffer_unordered(self.concurrency.max(1))
+                .try_collect()
+                .await?;
+            all.extend(page_prs);
+
+            page += 1;
+        }
+
+        Ok(all)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repo_from_reference_strips_merge_request_suffix() {
+        assert_eq!(repo_from_reference("group/project!123".to_string()), "group/project");
+    }
+
+    #[test]
+    fn repo_from_reference_passes_through_without_bang() {
+        assert_eq!(repo_from_reference("group/project".to_string()), "group/project");
+    }
+}
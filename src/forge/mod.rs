@@ -0,0 +1,114 @@
+//! Forge abstraction so `pr_stats`/`report` aren't tied to GitHub's API
+//! shape. Each forge knows how to list an org's merged PRs (with enough
+//! detail to tell approved from not-approved); `report` only ever talks to
+//! the `Forge` trait.
+
+use std::{env, str::FromStr};
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use reqwest::{header::{HeaderMap, HeaderValue}, Client};
+
+use crate::{cache::CachedClient, Result};
+
+mod forgejo;
+mod github;
+mod gitlab;
+
+/// A single merged PR as seen by a forge: enough to bucket by repo, author,
+/// reviewer, or merge date, and to tell approved from not-approved.
+pub struct PRInfo {
+    pub repository: String,
+    pub author: String,
+    pub approved: bool,
+    /// Logins of everyone who approved this PR.
+    pub reviewers: Vec<String>,
+    pub merged_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait Forge {
+    /// All merged PRs for `org`, across every repo the forge reports on,
+    /// optionally restricted to PRs merged within `[since, until]`.
+    async fn merged_prs(&self, org: &str, since: Option<NaiveDate>, until: Option<NaiveDate>) -> Result<Vec<PRInfo>>;
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ForgeKind {
+    GitHub,
+    GitLab,
+    Forgejo,
+}
+
+impl FromStr for ForgeKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "github" => Ok(ForgeKind::GitHub),
+            "gitlab" => Ok(ForgeKind::GitLab),
+            "forgejo" => Ok(ForgeKind::Forgejo),
+            other => anyhow::bail!("unknown --forge: {other} (expected github, gitlab, or forgejo)"),
+        }
+    }
+}
+
+impl ForgeKind {
+    fn default_base_url(self) -> &'static str {
+        match self {
+            ForgeKind::GitHub => "https://api.github.com",
+            ForgeKind::GitLab => "https://gitlab.com",
+            ForgeKind::Forgejo => "https://codeberg.org",
+        }
+    }
+
+    fn token_env(self) -> &'static str {
+        match self {
+            ForgeKind::GitHub => "GITHUB_TOKEN",
+            ForgeKind::GitLab => "GITLAB_TOKEN",
+            ForgeKind::Forgejo => "FORGEJO_TOKEN",
+        }
+    }
+}
+
+/// Build the client for `kind`, authenticating from `token_env` (or the
+/// forge's default env var) and pointed at `base_url` (or the forge's
+/// default host).
+///
+/// `concurrency` bounds the fan-out of GitLab's per-MR approvals lookups and
+/// Forgejo's per-PR reviews lookups. GitHub's collector gets its review data
+/// inline with the same GraphQL search query and pages strictly sequentially
+/// (each page's cursor depends on the previous one), so `concurrency` is
+/// unused there.
+pub fn build(
+    kind: ForgeKind,
+    base_url: Option<String>,
+    token_env: Option<String>,
+    no_cache: bool,
+    concurrency: usize,
+) -> Result<Box<dyn Forge>> {
+    let base_url = base_url.unwrap_or_else(|| kind.default_base_url().to_string());
+    let token_env = token_env.unwrap_or_else(|| kind.token_env().to_string());
+    let token = env::var(&token_env).ok();
+
+    let mut headers = HeaderMap::new();
+    if let Some(token) = &token {
+        log::info!("using token from {token_env}");
+        match kind {
+            ForgeKind::GitHub | ForgeKind::Forgejo => {
+                headers.insert("Authorization", HeaderValue::from_str(&format!("Bearer {token}"))?);
+            }
+            ForgeKind::GitLab => {
+                headers.insert("PRIVATE-TOKEN", HeaderValue::from_str(token)?);
+            }
+        }
+    }
+    let client = Client::builder().user_agent("gh-stats").default_headers(headers).build()?;
+    let client = CachedClient::new(client, !no_cache && CachedClient::enabled_by_env())?;
+
+    Ok(match kind {
+        ForgeKind::GitHub => Box::new(github::GitHub::new(client, base_url)),
+        ForgeKind::GitLab => Box::new(gitlab::GitLab::new(client, base_url, concurrency)),
+        ForgeKind::Forgejo => Box::new(forgejo::Forgejo::new(client, base_url, concurrency)),
+    })
+}
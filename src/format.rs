@@ -0,0 +1,117 @@
+//! Report output formats: `text` (the original one-line-per-repo output),
+//! `json`, `csv`, and an aligned `table`.
+
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::Result;
+
+#[derive(Serialize)]
+pub struct ReportRow {
+    /// The bucketed key: a repo, author, or reviewer login, depending on `--by`,
+    /// or a week/month label when `--bucket` is given.
+    pub repo: String,
+    pub approved: usize,
+    pub not_approved: usize,
+    pub total: usize,
+    pub rate: f32,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Table,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "table" => Ok(Format::Table),
+            other => anyhow::bail!("unknown --format: {other} (expected text, json, csv, or table)"),
+        }
+    }
+}
+
+pub fn emit(format: Format, rows: &[ReportRow], total: &ReportRow) -> Result<()> {
+    match format {
+        Format::Text => emit_text(rows, total),
+        Format::Json => emit_json(rows, total),
+        Format::Csv => emit_csv(rows, total),
+        Format::Table => emit_table(rows, total),
+    }
+}
+
+fn emit_text(rows: &[ReportRow], total: &ReportRow) -> Result<()> {
+    for row in rows {
+        println!(
+            "{}: total: {}, approved: {}, not approved: {}, rate: {:.2}",
+            row.repo, row.total, row.approved, row.not_approved, row.rate
+        );
+    }
+    println!(
+        "Total: total: {}, approved: {}, not approved: {}, rate: {:.2}",
+        total.total, total.approved, total.not_approved, total.rate
+    );
+    Ok(())
+}
+
+fn emit_json(rows: &[ReportRow], total: &ReportRow) -> Result<()> {
+    #[derive(Serialize)]
+    struct Report<'a> {
+        repos: &'a [ReportRow],
+        total: &'a ReportRow,
+    }
+    println!("{}", serde_json::to_string_pretty(&Report { repos: rows, total })?);
+    Ok(())
+}
+
+fn emit_csv(rows: &[ReportRow], total: &ReportRow) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    writer.serialize(total)?;
+    writer.flush()?;
+    Ok(())
+}
+
+fn emit_table(rows: &[ReportRow], total: &ReportRow) -> Result<()> {
+    println!("{:<40} {:>8} {:>12} {:>12} {:>6}", "repo", "approved", "not_approved", "total", "rate");
+    for row in rows {
+        println!(
+            "{:<40} {:>8} {:>12} {:>12} {:>6.2}",
+            row.repo, row.approved, row.not_approved, row.total, row.rate
+        );
+    }
+    println!(
+        "{:<40} {:>8} {:>12} {:>12} {:>6.2}",
+        total.repo, total.approved, total.not_approved, total.total, total.rate
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_from_str_parses_known_values() {
+        assert!(matches!("text".parse::<Format>().unwrap(), Format::Text));
+        assert!(matches!("json".parse::<Format>().unwrap(), Format::Json));
+        assert!(matches!("csv".parse::<Format>().unwrap(), Format::Csv));
+        assert!(matches!("table".parse::<Format>().unwrap(), Format::Table));
+    }
+
+    #[test]
+    fn format_from_str_rejects_unknown_value() {
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
@@ -1,71 +1,71 @@
-use std::{collections::HashMap, env, fmt::Display};
+use std::{collections::{BTreeMap, HashMap}, str::FromStr};
 
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use format::ReportRow;
+use forge::PRInfo;
 use itertools::Itertools;
-use reqwest::{header::{HeaderMap, HeaderValue}, Client};
-use serde::Deserialize;
-use tokio::{task::JoinSet, try_join};
+
+mod cache;
+mod forge;
+mod format;
+mod retry;
+
+pub use forge::ForgeKind;
+pub use format::Format;
 
 type Result<T> = anyhow::Result<T>;
 
-#[derive(Deserialize)]
-struct PRList {
-    total_count: u32,
-    items: Vec<PRInfo>,
+/// What dimension to bucket PRs by when reporting.
+#[derive(Clone, Copy, Debug)]
+pub enum By {
+    Repo,
+    Author,
+    Reviewer,
 }
 
-#[derive(Deserialize)]
-struct PRInfo {
-    repository_url: String,
-}
+impl FromStr for By {
+    type Err = anyhow::Error;
 
-fn search_url(query: &str, page: u32) -> String {
-    format!(
-        "https://api.github.com/search/issues?q={}&per_page=100&page={}",
-        query, page
-    )
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "repo" => Ok(By::Repo),
+            "author" => Ok(By::Author),
+            "reviewer" => Ok(By::Reviewer),
+            other => anyhow::bail!("unknown --by: {other} (expected repo, author, or reviewer)"),
+        }
+    }
 }
 
-async fn prs_from_search(client: &Client, query: impl Into<String>) -> Result<Vec<PRInfo>> {
-    let mut all = Vec::new();
-    let per_page = 100;
-    let query = query.into();
-    // get first response, which tells how many other requests to make
-    let resp: PRList = client
-        .get(search_url(&query, 1))
-        .send()
-        .await?
-        .json()
-        .await?;
-    all.extend(resp.items);
-
-    // issue all other page requests in parallel
-    let num_pages = (resp.total_count as f32 / per_page as f32).ceil() as u32;
-    log::debug!("first page returned from query {query}, total count is: {}, num pages: {num_pages}", resp.total_count);
-
-    let mut paged_res: JoinSet<_> = (2..=num_pages)
-        .map(|page| client.get(search_url(&query, page)).send())
-        .collect();
-
-    while let Some(res) = paged_res.join_next().await {
-        let resp: PRList = res??.json().await?;
-        all.extend(resp.items);
-    }
-    Ok(all)
+/// Time window to group merged PRs into for `--bucket`, overriding `--by`.
+#[derive(Clone, Copy, Debug)]
+pub enum Bucket {
+    Week,
+    Month,
 }
 
-fn count_by_pr(prs: &[PRInfo]) -> HashMap<&str, usize> {
-    prs.iter().map(|pr| pr.repository_url.as_str()).counts()
+impl FromStr for Bucket {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "week" => Ok(Bucket::Week),
+            "month" => Ok(Bucket::Month),
+            other => anyhow::bail!("unknown --bucket: {other} (expected week or month)"),
+        }
+    }
 }
 
-fn make_client() -> Result<Client> {
-    let mut headers = HeaderMap::new();
-    if let Ok(token) = env::var("GITHUB_TOKEN") {
-        log::info!("Using token from GITHUB_TOKEN");
-        let value = HeaderValue::from_str(&format!("Bearer {}", token))?;
-        headers.insert("Authorization", value);
+impl Bucket {
+    fn label(self, merged_at: DateTime<Utc>) -> String {
+        let date = merged_at.date_naive();
+        match self {
+            Bucket::Month => format!("{:04}-{:02}", date.year(), date.month()),
+            Bucket::Week => {
+                let week = date.iso_week();
+                format!("{:04}-W{:02}", week.year(), week.week())
+            }
+        }
     }
-    let client = Client::builder().user_agent("rust-agent").default_headers(headers).build()?;
-    Ok(client)
 }
 
 struct PRStats {
@@ -78,63 +78,156 @@ impl PRStats {
         PRStats { approved: 0, not_approved: 0 }
     }
 
-    fn new_with(approved: usize, not_approved: usize) -> Self {
-        PRStats { approved, not_approved }
-    }
-
     fn update_from(&mut self, other: &PRStats) {
         self.approved += other.approved;
         self.not_approved += other.not_approved;
     }
+
+    fn into_row(self, repo: String) -> ReportRow {
+        let total = self.approved + self.not_approved;
+        ReportRow {
+            repo,
+            approved: self.approved,
+            not_approved: self.not_approved,
+            total,
+            rate: self.approved as f32 / total as f32,
+        }
+    }
+}
+
+fn record(stats: &mut PRStats, approved: bool) {
+    if approved {
+        stats.approved += 1;
+    } else {
+        stats.not_approved += 1;
+    }
 }
 
-impl Display for PRStats {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "total: {}, approved: {}, not approved: {}, rate: {:.2}",
-            self.approved + self.not_approved,
-            self.approved,
-            self.not_approved,
-            self.approved as f32 / (self.approved + self.not_approved) as f32
-        )
+fn by_dimension(prs: &[PRInfo], by: By) -> HashMap<String, PRStats> {
+    let mut combined: HashMap<String, PRStats> = HashMap::new();
+    match by {
+        By::Repo => {
+            for pr in prs {
+                record(combined.entry(pr.repository.clone()).or_insert_with(PRStats::new), pr.approved);
+            }
+        }
+        By::Author => {
+            for pr in prs {
+                record(combined.entry(pr.author.clone()).or_insert_with(PRStats::new), pr.approved);
+            }
+        }
+        By::Reviewer => {
+            // We only know who approved, not who reviewed without approving,
+            // so this counts PRs approved per reviewer; `not_approved` stays 0
+            // and the reported rate is always 1.
+            for pr in prs {
+                for reviewer in &pr.reviewers {
+                    combined.entry(reviewer.clone()).or_insert_with(PRStats::new).approved += 1;
+                }
+            }
+        }
     }
+    combined
 }
 
-type StatsMap = HashMap<String, PRStats>;
-
-async fn pr_stats(org: &str) -> Result<StatsMap> {
-    let client = make_client()?;
-    // Do both search queries in parallel
-    let (res_approved, res_not) = try_join!(
-        prs_from_search(&client, format!("is:pr is:merged review:approved org:{org}")),
-        prs_from_search(&client, format!("is:pr is:merged -review:approved org:{org}")),
-    )?;
-    let prs_approved = count_by_pr(&res_approved);
-    let prs_not_approved = count_by_pr(&res_not);
-    // Combine the counts. Note that we don't necessarily know that the repos
-    // will be fully in both sets, hence we chain the keys which may yield
-    // repeats but covers all of them.
-    let mut combined = HashMap::new();
-    for repo in prs_approved.keys().chain(prs_not_approved.keys()) {
-        combined.entry(repo.to_string()).or_insert_with(|| {
-            PRStats::new_with(
-                *prs_approved.get(repo).unwrap_or(&0),
-                *prs_not_approved.get(repo).unwrap_or(&0))
-        });
+fn by_bucket(prs: &[PRInfo], bucket: Bucket) -> BTreeMap<String, PRStats> {
+    let mut combined: BTreeMap<String, PRStats> = BTreeMap::new();
+    for pr in prs {
+        record(combined.entry(bucket.label(pr.merged_at)).or_insert_with(PRStats::new), pr.approved);
     }
-    Ok(combined)
+    combined
 }
 
-pub async fn report(org: &str) -> Result<()> {
-    let combined = pr_stats(org).await?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(repository: &str, author: &str, approved: bool, reviewers: &[&str], merged_at: &str) -> PRInfo {
+        PRInfo {
+            repository: repository.to_string(),
+            author: author.to_string(),
+            approved,
+            reviewers: reviewers.iter().map(|r| r.to_string()).collect(),
+            merged_at: merged_at.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn by_dimension_repo_splits_approved_and_not() {
+        let prs = vec![
+            pr("a/one", "alice", true, &["bob"], "2026-01-01T00:00:00Z"),
+            pr("a/one", "alice", false, &[], "2026-01-02T00:00:00Z"),
+            pr("a/two", "carol", true, &["bob"], "2026-01-03T00:00:00Z"),
+        ];
+        let combined = by_dimension(&prs, By::Repo);
+        assert_eq!(combined["a/one"].approved, 1);
+        assert_eq!(combined["a/one"].not_approved, 1);
+        assert_eq!(combined["a/two"].approved, 1);
+        assert_eq!(combined["a/two"].not_approved, 0);
+    }
+
+    #[test]
+    fn by_dimension_reviewer_counts_per_approver_not_per_pr() {
+        let prs = vec![
+            pr("a/one", "alice", true, &["bob", "carol"], "2026-01-01T00:00:00Z"),
+            pr("a/two", "alice", true, &["bob"], "2026-01-02T00:00:00Z"),
+        ];
+        let combined = by_dimension(&prs, By::Reviewer);
+        assert_eq!(combined["bob"].approved, 2);
+        assert_eq!(combined["carol"].approved, 1);
+        assert!(!combined.contains_key("alice"));
+    }
+
+    #[test]
+    fn bucket_label_formats_month_and_week() {
+        let merged_at: DateTime<Utc> = "2026-03-05T00:00:00Z".parse().unwrap();
+        assert_eq!(Bucket::Month.label(merged_at), "2026-03");
+        assert_eq!(Bucket::Week.label(merged_at), "2026-W10");
+    }
+
+    #[test]
+    fn by_bucket_groups_by_label() {
+        let prs = vec![
+            pr("a/one", "alice", true, &[], "2026-01-05T00:00:00Z"),
+            pr("a/one", "alice", false, &[], "2026-01-20T00:00:00Z"),
+            pr("a/one", "alice", true, &[], "2026-02-01T00:00:00Z"),
+        ];
+        let combined = by_bucket(&prs, Bucket::Month);
+        assert_eq!(combined["2026-01"].approved, 1);
+        assert_eq!(combined["2026-01"].not_approved, 1);
+        assert_eq!(combined["2026-02"].approved, 1);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn report(
+    org: &str,
+    forge: ForgeKind,
+    base_url: Option<String>,
+    token_env: Option<String>,
+    no_cache: bool,
+    format: Format,
+    by: By,
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+    bucket: Option<Bucket>,
+    concurrency: usize,
+) -> Result<()> {
+    let forge = forge::build(forge, base_url, token_env, no_cache, concurrency)?;
+    let prs = forge.merged_prs(org, since, until).await?;
+    log::debug!("fetched {} merged PRs for org {org}", prs.len());
+
+    let combined: Vec<(String, PRStats)> = match bucket {
+        Some(bucket) => by_bucket(&prs, bucket).into_iter().collect(),
+        None => by_dimension(&prs, by).into_iter().sorted_by(|a, b| a.0.cmp(&b.0)).collect(),
+    };
+
     let mut tot_stats = PRStats::new();
-    for (repo, stats) in combined.iter().sorted_by_key(|a| a.0) {
-        println!(
-            "{repo}: {stats}"
-        );
-        tot_stats.update_from(stats);
+    let mut rows = Vec::with_capacity(combined.len());
+    for (key, stats) in combined {
+        tot_stats.update_from(&stats);
+        rows.push(stats.into_row(key));
     }
-    println!("Total: {tot_stats}");
-    Ok(())
+    let total_row = tot_stats.into_row("Total".to_string());
+    format::emit(format, &rows, &total_row)
 }
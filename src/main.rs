@@ -1,9 +1,60 @@
-use gh_stats::report;
+use chrono::NaiveDate;
+use gh_stats::{report, Bucket, By, ForgeKind, Format};
 
 #[tokio::main(flavor = "current_thread")]
 async fn main() {
     env_logger::init();
-    let org = std::env::args().nth(1).expect("positional argument required: org name");
+    let mut args = std::env::args().skip(1);
+    let mut org = None;
+    let mut no_cache = false;
+    let mut format = Format::Text;
+    let mut forge = ForgeKind::GitHub;
+    let mut base_url = None;
+    let mut token_env = None;
+    let mut by = By::Repo;
+    let mut since = None;
+    let mut until = None;
+    let mut bucket = None;
+    let mut concurrency = 5;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--no-cache" => no_cache = true,
+            "--format" => {
+                let value = args.next().expect("--format requires a value");
+                format = value.parse().expect("invalid --format value");
+            }
+            "--forge" => {
+                let value = args.next().expect("--forge requires a value");
+                forge = value.parse().expect("invalid --forge value");
+            }
+            "--base-url" => base_url = Some(args.next().expect("--base-url requires a value")),
+            "--token-env" => token_env = Some(args.next().expect("--token-env requires a value")),
+            "--by" => {
+                let value = args.next().expect("--by requires a value");
+                by = value.parse().expect("invalid --by value");
+            }
+            "--since" => {
+                let value = args.next().expect("--since requires a value");
+                since = Some(NaiveDate::parse_from_str(&value, "%Y-%m-%d").expect("invalid --since date"));
+            }
+            "--until" => {
+                let value = args.next().expect("--until requires a value");
+                until = Some(NaiveDate::parse_from_str(&value, "%Y-%m-%d").expect("invalid --until date"));
+            }
+            "--bucket" => {
+                let value = args.next().expect("--bucket requires a value");
+                bucket = Some(value.parse().expect("invalid --bucket value"));
+            }
+            "--concurrency" => {
+                let value = args.next().expect("--concurrency requires a value");
+                concurrency = value.parse().expect("invalid --concurrency value");
+            }
+            _ => org = Some(arg),
+        }
+    }
+    let org = org.expect("positional argument required: org name");
     log::info!("reporting for org: {org}");
-    report(&org).await.unwrap();
+    report(&org, forge, base_url, token_env, no_cache, format, by, since, until, bucket, concurrency)
+        .await
+        .unwrap();
 }
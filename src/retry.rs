@@ -0,0 +1,113 @@
+//! Retry/backoff for the one place every forge sends an HTTP request:
+//! [`crate::cache::CachedClient`]. Only 403/429 responses that actually
+//! carry a rate-limit signal (`Retry-After`, or `X-RateLimit-Remaining: 0`
+//! plus `X-RateLimit-Reset`) are retried, with the wait capped and jittered
+//! so a generous reset header or many requests resetting at once don't
+//! cause an absurd sleep or a thundering herd. A 403/429 with neither
+//! signal is a permanent failure (bad token, no access) and is returned
+//! immediately instead of being retried.
+
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::{Response, StatusCode};
+
+const MAX_RETRIES: u32 = 5;
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+pub async fn send_with_retry<F, Fut>(send: F) -> reqwest::Result<Response>
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = reqwest::Result<Response>>,
+{
+    let mut attempt = 0;
+    loop {
+        let resp = send().await?;
+        let status = resp.status();
+        if status != StatusCode::FORBIDDEN && status != StatusCode::TOO_MANY_REQUESTS {
+            return Ok(resp);
+        }
+        let Some(wait) = retry_after(&resp) else {
+            // No rate-limit signal on this 403/429: it's a permanent failure
+            // (bad token, no org access, ...), not something retrying fixes.
+            return Ok(resp);
+        };
+        if attempt >= MAX_RETRIES {
+            return Ok(resp);
+        }
+
+        let wait = backoff(wait);
+        log::warn!("rate limited (status {status}), retrying in {wait:?}");
+        tokio::time::sleep(wait).await;
+        attempt += 1;
+    }
+}
+
+/// `Retry-After`/`X-RateLimit-Reset`-derived wait if this response actually
+/// signals a rate limit, `None` otherwise.
+fn retry_after(resp: &Response) -> Option<Duration> {
+    if let Some(secs) = header_u64(resp, "retry-after") {
+        return Some(Duration::from_secs(secs));
+    }
+    if header_u64(resp, "x-ratelimit-remaining") == Some(0) {
+        let reset = header_u64(resp, "x-ratelimit-reset")? as i64;
+        let secs = (reset - chrono::Utc::now().timestamp()).max(1) as u64;
+        return Some(Duration::from_secs(secs));
+    }
+    None
+}
+
+fn header_u64(resp: &Response, name: &str) -> Option<u64> {
+    resp.headers().get(name)?.to_str().ok()?.parse().ok()
+}
+
+/// Adds jitter to `wait` and caps it, so a generous reset header doesn't
+/// stall the report and concurrent requests don't retry in lockstep.
+fn backoff(wait: Duration) -> Duration {
+    let jitter = Duration::from_millis(rand::thread_rng().gen_range(0..500));
+    (wait + jitter).min(MAX_BACKOFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(headers: &[(&str, &str)]) -> Response {
+        let mut builder = http::Response::builder().status(403);
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        Response::from(builder.body(Vec::new()).unwrap())
+    }
+
+    #[test]
+    fn retry_after_uses_retry_after_header() {
+        let resp = response(&[("retry-after", "30")]);
+        assert_eq!(retry_after(&resp), Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn retry_after_uses_rate_limit_reset_when_exhausted() {
+        let reset = chrono::Utc::now().timestamp() + 10;
+        let resp = response(&[("x-ratelimit-remaining", "0"), ("x-ratelimit-reset", &reset.to_string())]);
+        let wait = retry_after(&resp).expect("rate-limit headers should signal a retry");
+        assert!(wait.as_secs() <= 10);
+    }
+
+    #[test]
+    fn retry_after_is_none_without_a_signal() {
+        let resp = response(&[]);
+        assert_eq!(retry_after(&resp), None);
+    }
+
+    #[test]
+    fn retry_after_is_none_when_not_exhausted() {
+        let resp = response(&[("x-ratelimit-remaining", "5")]);
+        assert_eq!(retry_after(&resp), None);
+    }
+
+    #[test]
+    fn backoff_caps_at_max_backoff() {
+        assert_eq!(backoff(Duration::from_secs(3600)), MAX_BACKOFF);
+    }
+}